@@ -1,4 +1,4 @@
-use crate::manifest::yaml::Network;
+use crate::manifest::yaml::{Network, Transport};
 use crate::types::code::Code;
 
 /// Generates the provider name for a given network.
@@ -30,6 +30,22 @@ fn network_provider_name_from_name(network_name: &str) -> String {
     )
 }
 
+/// Generates the WebSocket provider name for a given network.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+///
+/// # Returns
+///
+/// A `String` representing the WebSocket provider name.
+fn network_ws_provider_name(network: &Network) -> String {
+    format!(
+        "{network_name}_WS_PROVIDER",
+        network_name = network.name.to_uppercase()
+    )
+}
+
 /// Generates the function name for the network provider.
 ///
 /// # Arguments
@@ -46,6 +62,188 @@ pub fn network_provider_fn_name(network: &Network) -> String {
     )
 }
 
+/// Generates the function name for the network WebSocket provider.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+///
+/// # Returns
+///
+/// A `String` representing the function name for the network WebSocket provider.
+pub fn network_ws_provider_fn_name(network: &Network) -> String {
+    format!(
+        "get_{fn_name}",
+        fn_name = network_ws_provider_name(network).to_lowercase()
+    )
+}
+
+/// Returns `true` if the given URL speaks the WebSocket protocol.
+///
+/// # Arguments
+///
+/// * `url` - The RPC endpoint URL.
+///
+/// # Returns
+///
+/// A `bool` indicating whether the URL is a `ws://` or `wss://` endpoint.
+fn url_is_ws(url: &str) -> bool {
+    url.starts_with("ws://") || url.starts_with("wss://")
+}
+
+/// Returns `true` if the network should be served over a WebSocket provider.
+///
+/// A network tails over WebSockets when its `transport` is explicitly set to
+/// `ws`, or when its `url` carries a `ws://`/`wss://` scheme.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+///
+/// # Returns
+///
+/// A `bool` indicating whether the primary transport is WebSockets.
+fn network_uses_ws(network: &Network) -> bool {
+    network.transport == Some(Transport::Ws) || url_is_ws(&network.url)
+}
+
+/// Asserts a network's transport configuration is internally consistent.
+///
+/// A `ws_url` pairs a live WebSocket with an HTTP `url` used for historical
+/// `getLogs` backfill, so the primary transport of a hybrid network must be
+/// HTTP. Combining `ws_url` with a WebSocket primary (`transport: ws` or a
+/// `ws://`/`wss://` `url`) is contradictory and would silently drop the HTTP
+/// backfill provider, so the generator rejects it up front.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+fn assert_network_transport(network: &Network) {
+    if network.ws_url.is_some() && network_uses_ws(network) {
+        panic!(
+            "network '{}' pairs a `ws_url` with a WebSocket primary transport; \
+             `ws_url` adds live tailing alongside an HTTP backfill `url`, so the \
+             primary `url` must be HTTP",
+            network.name
+        );
+    }
+}
+
+/// Returns `true` if the network is served by an HTTP provider.
+///
+/// WebSocket-primary networks have no HTTP provider; every other network
+/// (including a hybrid one, whose primary `url` is HTTP) does.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+///
+/// # Returns
+///
+/// A `bool` indicating whether an HTTP provider is emitted.
+fn network_has_http(network: &Network) -> bool {
+    !network_uses_ws(network)
+}
+
+/// Returns `true` if the network is served by a WebSocket provider.
+///
+/// This covers both a WebSocket-primary network and a hybrid network whose
+/// `ws_url` adds a live-tailing provider alongside the HTTP one.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+///
+/// # Returns
+///
+/// A `bool` indicating whether a WebSocket provider is emitted.
+fn network_has_ws(network: &Network) -> bool {
+    network_uses_ws(network) || network.ws_url.is_some()
+}
+
+/// Builds the ordered, quoted endpoint list for a network's HTTP provider.
+///
+/// The primary `url` always leads the rotation; any additional `urls` that are
+/// not already listed follow in their configured order. The result is the
+/// contents of a `&[String]` slice literal passed to `create_retry_client`.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+///
+/// # Returns
+///
+/// A `String` of comma-separated endpoint expressions.
+fn network_http_endpoints(network: &Network) -> String {
+    let mut endpoints = vec![network.url.clone()];
+    if let Some(urls) = &network.urls {
+        for url in urls {
+            if !endpoints.contains(url) {
+                endpoints.push(url.clone());
+            }
+        }
+    }
+
+    endpoints
+        .iter()
+        .map(|url| resolve_string_expr(url))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Builds the owned-`String` expression for a URL or header value.
+///
+/// The raw value — `${VAR}` placeholders included — is emitted as an escaped
+/// Rust string literal. Resolution happens at construction time inside
+/// `create_retry_client`/`create_ws_client`, which read the variable from
+/// `std::env` (erroring clearly when unset) so the secret never lands in the
+/// committed source.
+///
+/// # Arguments
+///
+/// * `value` - The raw URL or header value from the manifest.
+///
+/// # Returns
+///
+/// A `String` containing the Rust expression.
+fn resolve_string_expr(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\".to_string()", escaped)
+}
+
+/// Builds the quoted header list for a network's HTTP provider.
+///
+/// A convenience `auth_header` becomes an `Authorization` entry and is merged
+/// with any explicit `headers` map. Values carry their raw `${VAR}`
+/// placeholders, which `create_retry_client` resolves from the environment. The
+/// result is the contents of a `&[(&str, String)]` slice literal passed to
+/// `create_retry_client`.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+///
+/// # Returns
+///
+/// A `String` of comma-separated `(name, value)` header tuples.
+fn network_headers(network: &Network) -> String {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    if let Some(auth_header) = &network.auth_header {
+        headers.push(("Authorization".to_string(), auth_header.clone()));
+    }
+    if let Some(extra) = &network.headers {
+        for (name, value) in extra {
+            headers.push((name.clone(), value.clone()));
+        }
+    }
+
+    headers
+        .iter()
+        .map(|(name, value)| format!("(\"{}\", {})", name, resolve_string_expr(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Generates the function name for the network provider from the network name.
 ///
 /// # Arguments
@@ -69,37 +267,125 @@ pub fn network_provider_fn_name_by_name(network_name: &str) -> String {
 /// * `network` - A reference to the `Network` configuration.
 ///
 fn generate_network_lazy_provider_code(network: &Network) -> Code {
+    assert_network_transport(network);
+
+    // WebSocket providers connect on the caller's async runtime and so are
+    // emitted as lazily-initialised accessors, not eager `lazy_static` entries.
+    if !network_has_http(network) {
+        return Code::new(String::new());
+    }
+
+    let compute_units_per_second =
+        if let Some(compute_units_per_second) = network.compute_units_per_second {
+            format!("Some({})", compute_units_per_second)
+        } else {
+            "None".to_string()
+        };
+
+    // The HTTP provider rotates across every configured endpoint. Requests go to
+    // the current primary and advance to the next endpoint on a transport/5xx/429
+    // error, applying per-endpoint exponential backoff with jitter so a flaky or
+    // rate-limited RPC self-heals instead of stalling the whole indexer.
     Code::new(format!(
         r#"
-            static ref {network_name}: Arc<Provider<RetryClient<Http>>> = create_retry_client("{network_url}", {compute_units_per_second}).expect("Error creating provider");
+            static ref {network_name}: Arc<Provider<FailoverClient>> = create_retry_client(&[{endpoints}], {compute_units_per_second}, &[{headers}]).expect("Error creating provider");
         "#,
         network_name = network_provider_name(network),
-        network_url = network.url,
-        compute_units_per_second =
-            if let Some(compute_units_per_second) = network.compute_units_per_second {
-                format!("Some({})", compute_units_per_second)
-            } else {
-                "None".to_string()
-            }
+        endpoints = network_http_endpoints(network),
+        compute_units_per_second = compute_units_per_second,
+        headers = network_headers(network),
     ))
 }
 
-/// Generates the provider function code for a given network.
+/// Generates the WebSocket `OnceCell` static backing a network's lazy WS
+/// accessor.
+///
+/// The socket is established on the caller's runtime on first access, so the
+/// provider is held behind a `tokio::sync::OnceCell` rather than a
+/// `lazy_static` (which would connect on a runtime that is dropped immediately).
 ///
 /// # Arguments
 ///
 /// * `network` - A reference to the `Network` configuration.
 ///
-fn generate_network_provider_code(network: &Network) -> Code {
+fn generate_network_ws_static_code(network: &Network) -> Code {
+    if !network_has_ws(network) {
+        return Code::new(String::new());
+    }
+
+    // A WebSocket-primary network owns the primary provider slot; a hybrid
+    // network parks its live-tailing socket in the dedicated `_WS_PROVIDER` slot.
+    let ws_name = if network_uses_ws(network) {
+        network_provider_name(network)
+    } else {
+        network_ws_provider_name(network)
+    };
+
     Code::new(format!(
         r#"
-            pub fn {fn_name}() -> Arc<Provider<RetryClient<Http>>> {{
+            static {ws_name}: OnceCell<Arc<Provider<Ws>>> = OnceCell::const_new();
+        "#,
+        ws_name = ws_name,
+    ))
+}
+
+/// Generates the provider function code for a given network.
+///
+/// # Arguments
+///
+/// * `network` - A reference to the `Network` configuration.
+///
+fn generate_network_provider_code(network: &Network) -> Code {
+    assert_network_transport(network);
+
+    let mut code = Code::new(String::new());
+
+    if network_has_http(network) {
+        code.push_str(&Code::new(format!(
+            r#"
+            pub fn {fn_name}() -> Arc<Provider<FailoverClient>> {{
                 {provider_lazy_name}.clone()
             }}
         "#,
-        fn_name = network_provider_fn_name(network),
-        provider_lazy_name = network_provider_name(network)
-    ))
+            fn_name = network_provider_fn_name(network),
+            provider_lazy_name = network_provider_name(network)
+        )));
+    }
+
+    // The WebSocket provider connects lazily on first access so the socket lives
+    // on the caller's runtime; `get_or_try_init` surfaces a connection failure
+    // as a handled error instead of aborting at construction time.
+    if network_has_ws(network) {
+        let (fn_name, static_name, url) = if network_uses_ws(network) {
+            (
+                network_provider_fn_name(network),
+                network_provider_name(network),
+                network.url.as_str(),
+            )
+        } else {
+            (
+                network_ws_provider_fn_name(network),
+                network_ws_provider_name(network),
+                network.ws_url.as_deref().unwrap_or_default(),
+            )
+        };
+
+        code.push_str(&Code::new(format!(
+            r#"
+            pub async fn {fn_name}() -> Result<Arc<Provider<Ws>>, Box<dyn std::error::Error>> {{
+                {static_name}
+                    .get_or_try_init(|| create_ws_client({url_expr}))
+                    .await
+                    .cloned()
+            }}
+        "#,
+            fn_name = fn_name,
+            static_name = static_name,
+            url_expr = resolve_string_expr(url),
+        )));
+    }
+
+    code
 }
 
 /// Generates the code for all network providers.
@@ -112,30 +398,182 @@ fn generate_network_provider_code(network: &Network) -> Code {
 ///
 /// The generated network providers code.
 pub fn generate_networks_code(networks: &[Network]) -> Code {
-    let mut output = Code::new(r#"
+    // Only emit the HTTP lazy statics, client constructor, and failover type
+    // when at least one network is served over HTTP; a WebSocket-only manifest
+    // would otherwise trip `unused_imports` under `-D warnings`.
+    let needs_http = networks.iter().any(network_has_http);
+    let http_imports = if needs_http {
+        r#"use rindexer_core::lazy_static;
+            use rindexer_core::provider::{create_retry_client, FailoverClient};
+            "#
+    } else {
+        ""
+    };
+
+    // Only pull in the WebSocket types/constructor when a network actually tails
+    // over `ws`, so HTTP-only generated trees stay free of unused imports.
+    let needs_ws = networks.iter().any(network_has_ws);
+    let ws_imports = if needs_ws {
+        r#"use ethers::providers::Ws;
+            use rindexer_core::provider::create_ws_client;
+            use tokio::sync::OnceCell;
+            "#
+    } else {
+        ""
+    };
+
+    let mut output = Code::new(format!(r#"
             /// THIS IS A GENERATED FILE. DO NOT MODIFY MANUALLY.
             ///
             /// This file was auto generated by rindexer - https://github.com/joshstevens19/rindexer.
             /// Any manual changes to this file will be overwritten.
-            
-            use ethers::providers::{Provider, Http, RetryClient};
-            use rindexer_core::lazy_static;
-            use rindexer_core::provider::create_retry_client;
+
+            use ethers::providers::{{Provider, Middleware}};
+            {http_imports}{ws_imports}use rindexer_core::tracing::info;
             use std::sync::Arc;
+        "#,
+        http_imports = http_imports,
+        ws_imports = ws_imports,
+    ));
 
-            lazy_static! {
-        "#
-    .to_string());
+    // HTTP providers are eagerly initialised behind `lazy_static`; skip the block
+    // entirely for a WebSocket-only manifest.
+    if needs_http {
+        output.push_str(&Code::new("lazy_static! {\n".to_string()));
+        for network in networks {
+            output.push_str(&generate_network_lazy_provider_code(network));
+        }
+        output.push_str(&Code::new("}\n".to_string()));
+    }
 
+    // WebSocket providers are held behind `OnceCell`s connected on first use.
     for network in networks {
-        output.push_str(&generate_network_lazy_provider_code(network));
+        output.push_str(&generate_network_ws_static_code(network));
     }
 
-    output.push_str(&Code::new("}".to_string()));
-
     for network in networks {
         output.push_str(&generate_network_provider_code(network));
     }
 
+    output.push_str(&generate_validate_networks_code(networks));
+
     output
 }
+
+/// Generates a single provider probe for [`generate_validate_networks_code`].
+///
+/// `acquire` is the expression that yields the `Arc`-wrapped provider (a bare
+/// accessor call for the synchronous HTTP provider, or `…().await?` for an
+/// async WebSocket accessor). The probe issues an `eth_chainId` plus a
+/// head-block ping, fails fast naming the provider on a chain-id mismatch, and
+/// logs the resolved endpoint and current head block.
+///
+/// # Arguments
+///
+/// * `name` - The network name, used in log and error messages.
+/// * `url` - The endpoint URL being probed, as written in the manifest; it is
+///   resolved from the environment at probe time for log and error messages.
+/// * `chain_id` - The chain id the manifest expects the endpoint to serve.
+/// * `acquire` - The Rust expression acquiring the provider.
+///
+/// # Returns
+///
+/// A `String` containing the probe block.
+fn validate_probe_block(name: &str, url: &str, chain_id: u64, acquire: &str) -> String {
+    format!(
+        r#"
+                {{
+                    let provider = {acquire};
+                    let url = rindexer_core::provider::resolve_rpc_url(&{url_expr})?;
+                    let chain_id = provider
+                        .get_chainid()
+                        .await
+                        .map_err(|e| format!("network '{name}' provider ({{url}}) is unreachable: {{e}}"))?
+                        .as_u64();
+                    if chain_id != {chain_id} {{
+                        return Err(format!(
+                            "network '{name}' provider ({{url}}) served chain id {{}} but the manifest expects {{}}",
+                            chain_id, {chain_id}
+                        )
+                        .into());
+                    }}
+                    let head_block = provider
+                        .get_block_number()
+                        .await
+                        .map_err(|e| format!("network '{name}' provider ({{url}}) head block probe failed: {{e}}"))?;
+                    info!(
+                        "network '{name}' reachable at {{url}} (chain id {{}}, head block {{}})",
+                        chain_id, head_block
+                    );
+                }}
+"#,
+        name = name,
+        url_expr = resolve_string_expr(url),
+        chain_id = chain_id,
+        acquire = acquire,
+    )
+}
+
+/// Generates the `validate_networks` health-probe function.
+///
+/// On indexer boot the generated function walks every network provider — the
+/// HTTP provider and, for a hybrid network, the live-tailing WebSocket provider
+/// too — issuing an `eth_chainId` plus a head-block probe to confirm each
+/// endpoint is reachable and serving the chain the manifest expects. It fails
+/// fast naming the offending provider on a mismatch rather than silently
+/// indexing the wrong chain, and logs the resolved endpoint and current head
+/// block per provider so misconfigured RPC URLs surface immediately.
+///
+/// # Arguments
+///
+/// * `networks` - A reference to a slice of `Network` configurations.
+///
+/// # Returns
+///
+/// The generated `validate_networks` function code.
+fn generate_validate_networks_code(networks: &[Network]) -> Code {
+    let mut body = String::new();
+    for network in networks {
+        // The synchronous HTTP provider is probed by a bare accessor call.
+        if network_has_http(network) {
+            body.push_str(&validate_probe_block(
+                &network.name,
+                &network.url,
+                network.chain_id,
+                &format!("{}()", network_provider_fn_name(network)),
+            ));
+        }
+
+        // The WebSocket provider connects lazily, so its accessor is async and
+        // fallible; probe it too so a misconfigured live-tailing endpoint is
+        // caught on boot rather than after hours of empty results.
+        if network_has_ws(network) {
+            let (fn_name, url) = if network_uses_ws(network) {
+                (network_provider_fn_name(network), network.url.as_str())
+            } else {
+                (
+                    network_ws_provider_fn_name(network),
+                    network.ws_url.as_deref().unwrap_or_default(),
+                )
+            };
+            body.push_str(&validate_probe_block(
+                &network.name,
+                url,
+                network.chain_id,
+                &format!("{}().await?", fn_name),
+            ));
+        }
+    }
+
+    Code::new(format!(
+        r#"
+            /// Probes every configured network provider on boot, asserting each
+            /// endpoint is reachable and serving the chain id the manifest expects.
+            pub async fn validate_networks() -> Result<(), Box<dyn std::error::Error>> {{
+                {body}
+                Ok(())
+            }}
+        "#,
+        body = body.trim_end(),
+    ))
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// The transport a network uses to talk to its RPC endpoint.
+///
+/// When the manifest omits the field the transport is inferred from the `url`
+/// scheme. Setting `transport: ws` (or pointing `url` at a `ws://`/`wss://` endpoint) switches a
+/// network onto `eth_subscribe`-based streaming instead of polling.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    /// Poll over JSON-RPC via HTTP(S).
+    Http,
+    /// Tail over a persistent WebSocket using `eth_subscribe`.
+    Ws,
+}
+
+/// A single network the indexer connects to, as declared in the manifest.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Network {
+    /// The manifest-local name, used to key the generated provider accessors.
+    pub name: String,
+
+    /// The chain id the manifest expects this endpoint to serve. Asserted
+    /// against the live `eth_chainId` response on boot so a misconfigured RPC
+    /// URL fails fast instead of silently indexing the wrong chain.
+    pub chain_id: u64,
+
+    /// The primary RPC endpoint. An `http(s)://` URL is polled; a `ws(s)://`
+    /// URL (or an explicit [`Transport::Ws`]) is tailed live.
+    pub url: String,
+
+    /// Additional HTTP endpoints for the same chain. Combined with `url` (which
+    /// always leads) they form an ordered rotation the retry client fails over
+    /// across when the current primary is flaky or rate-limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub urls: Option<Vec<String>>,
+
+    /// The transport override. `ws` forces a WebSocket provider; `http` does
+    /// not override a `ws://`/`wss://` `url`. When absent the transport is
+    /// inferred from the `url` scheme.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<Transport>,
+
+    /// An optional WebSocket endpoint paired with an HTTP `url` to form a hybrid
+    /// network: the HTTP provider serves historical `getLogs` backfill while this
+    /// provider tails new blocks and logs live.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws_url: Option<String>,
+
+    /// A convenience `Authorization` header value attached to every HTTP
+    /// request, e.g. `Bearer ${API_KEY}`. `${VAR}` placeholders are resolved
+    /// from the environment at provider construction so the secret never lands
+    /// in the generated source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_header: Option<String>,
+
+    /// Additional HTTP headers attached to every request. Values support the
+    /// same `${VAR}` environment resolution as [`Network::auth_header`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<std::collections::BTreeMap<String, String>>,
+
+    /// The Alchemy-style compute-units-per-second budget applied to the retry
+    /// client, when the provider enforces one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute_units_per_second: Option<u64>,
+}
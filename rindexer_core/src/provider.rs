@@ -0,0 +1,375 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use ethers::providers::{Http, HttpClientError, JsonRpcClient, Provider, Ws};
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::Serialize;
+use url::Url;
+
+/// The maximum number of times the WebSocket transport silently reconnects
+/// before surfacing the error to the caller.
+const WS_MAX_RECONNECTS: usize = usize::MAX;
+
+/// The shortest a failed endpoint cools down before it is retried.
+const BASE_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The longest an endpoint's backoff window is allowed to grow to.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The longest a single request may spend rotating and waiting on cooldowns
+/// before the last observed error is surfaced instead of retrying forever.
+const MAX_REQUEST_WAIT: Duration = Duration::from_secs(60);
+
+/// A shared token-bucket rate limiter enforcing a compute-units-per-second
+/// ceiling across all of a client's endpoints.
+///
+/// Each request draws one unit; the bucket refills at `rate` units per second
+/// up to a one-second burst, mirroring the budget the baseline `RetryClient`
+/// enforced. A per-request weight of one keeps the accounting simple — the
+/// budget acts as a request-rate ceiling rather than per-method CU weighting.
+struct RateLimiter {
+    rate: f64,
+    state: Mutex<Bucket>,
+}
+
+/// The mutable token-bucket state behind a [`RateLimiter`].
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Builds a limiter refilling at `rate` units per second.
+    fn new(rate: f64) -> Self {
+        Self {
+            rate,
+            state: Mutex::new(Bucket {
+                tokens: rate,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Waits until a single unit is available, then consumes it.
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.state.lock().unwrap();
+                let now = Instant::now();
+                let elapsed = now.saturating_duration_since(bucket.last_refill);
+                bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.rate).min(self.rate);
+                bucket.last_refill = now;
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// The per-endpoint rotation state backing [`FailoverClient`].
+struct Endpoint {
+    http: Http,
+    backoff: Mutex<Backoff>,
+}
+
+/// Tracks an endpoint's consecutive-failure count and cooldown window.
+#[derive(Default)]
+struct Backoff {
+    consecutive_failures: u32,
+    cooling_until: Option<Instant>,
+}
+
+impl Backoff {
+    /// Records a success, clearing any outstanding cooldown.
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.cooling_until = None;
+    }
+
+    /// Records a failure, growing the cooldown window exponentially (with a
+    /// small ±20% jitter) up to [`MAX_BACKOFF`].
+    fn record_failure(&mut self, now: Instant) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+
+        let exp = self.consecutive_failures.saturating_sub(1).min(31);
+        let scaled = BASE_BACKOFF
+            .checked_mul(1u32 << exp)
+            .unwrap_or(MAX_BACKOFF)
+            .min(MAX_BACKOFF);
+
+        // ±20% jitter avoids a thundering herd of synchronised retries.
+        let jitter = rand::thread_rng().gen_range(0.8..1.2);
+        let delay = scaled.mul_f64(jitter).min(MAX_BACKOFF);
+        self.cooling_until = Some(now + delay);
+    }
+
+    /// Returns the instant this endpoint leaves its cooldown window, or `None`
+    /// if it is currently eligible for selection.
+    fn cooling_until(&self, now: Instant) -> Option<Instant> {
+        self.cooling_until.filter(|&until| until > now)
+    }
+}
+
+/// A rotating, self-healing JSON-RPC transport over several HTTP endpoints.
+///
+/// Every request targets the current primary and, on a transport/5xx/429
+/// failure, advances to the next endpoint while the failing one cools down
+/// under per-endpoint exponential backoff. Cooling endpoints are skipped during
+/// selection; when every endpoint is cooling the client sleeps until the
+/// soonest one is ready. A successful response resets that endpoint's backoff.
+#[derive(Clone)]
+pub struct FailoverClient {
+    endpoints: Arc<Vec<Endpoint>>,
+    cursor: Arc<AtomicUsize>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl std::fmt::Debug for FailoverClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FailoverClient")
+            .field("endpoints", &self.endpoints.len())
+            .finish()
+    }
+}
+
+impl FailoverClient {
+    /// Picks the current primary, or the first eligible endpoint after it when
+    /// the primary is cooling down; that endpoint becomes the new primary.
+    ///
+    /// Returns `Ok(index)` for a ready endpoint, or `Err(instant)` with the
+    /// soonest cooldown expiry when every endpoint is currently cooling down.
+    fn select(&self) -> Result<usize, Instant> {
+        let now = Instant::now();
+        let len = self.endpoints.len();
+        let start = self.cursor.load(Ordering::Relaxed);
+
+        let mut soonest: Option<Instant> = None;
+        for offset in 0..len {
+            let idx = (start + offset) % len;
+            match self.endpoints[idx].backoff.lock().unwrap().cooling_until(now) {
+                None => {
+                    self.cursor.store(idx, Ordering::Relaxed);
+                    return Ok(idx);
+                }
+                Some(until) => {
+                    soonest = Some(soonest.map_or(until, |s: Instant| s.min(until)));
+                }
+            }
+        }
+
+        Err(soonest.unwrap_or(now))
+    }
+
+    /// Classifies whether an error warrants failing over to another endpoint.
+    ///
+    /// Transport problems and oversized/garbled responses rotate; a genuine
+    /// JSON-RPC error (a revert, bad params, …) is the same from every endpoint
+    /// and is returned to the caller unchanged.
+    fn should_failover(err: &HttpClientError) -> bool {
+        !matches!(err, HttpClientError::JsonRpcError(_))
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for FailoverClient {
+    type Error = HttpClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        // Serialize once so the same payload can be replayed across endpoints.
+        let params = serde_json::to_value(params).map_err(|err| HttpClientError::SerdeJson {
+            err,
+            text: String::new(),
+        })?;
+
+        // Bound total time spent rotating/waiting so a fully-down endpoint set
+        // surfaces the last error instead of hanging the request forever.
+        let deadline = Instant::now() + MAX_REQUEST_WAIT;
+        let mut last_err: Option<HttpClientError> = None;
+        loop {
+            let idx = match self.select() {
+                Ok(idx) => idx,
+                Err(until) => {
+                    // Every endpoint is cooling down; wait for the soonest one,
+                    // unless that would push us past the request deadline.
+                    if until >= deadline {
+                        return Err(last_err.unwrap_or_else(|| {
+                            HttpClientError::SerdeJson {
+                                err: serde_json::Error::custom(
+                                    "all endpoints are cooling down and no request was attempted",
+                                ),
+                                text: String::new(),
+                            }
+                        }));
+                    }
+                    let sleep = until.saturating_duration_since(Instant::now());
+                    tokio::time::sleep(sleep).await;
+                    continue;
+                }
+            };
+
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire().await;
+            }
+
+            let endpoint = &self.endpoints[idx];
+            match endpoint.http.request(method, params.clone()).await {
+                Ok(result) => {
+                    endpoint.backoff.lock().unwrap().record_success();
+                    return Ok(result);
+                }
+                Err(err) => {
+                    if !Self::should_failover(&err) {
+                        return Err(err);
+                    }
+                    endpoint.backoff.lock().unwrap().record_failure(Instant::now());
+                    last_err = Some(err);
+                    if Instant::now() >= deadline {
+                        return Err(last_err.expect("a failure was just recorded"));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Resolves `${VAR}` placeholders in an RPC URL or header value from the
+/// environment at construction time.
+///
+/// Each `${NAME}` is substituted with the value of the `NAME` environment
+/// variable. Keeping secrets out of the generated tree means the same
+/// generated artifact can run against different credentials per environment.
+///
+/// # Arguments
+///
+/// * `value` - The raw value, which may contain one or more `${VAR}` tokens.
+///
+/// # Returns
+///
+/// The resolved value, or a clear error naming the missing or malformed
+/// placeholder so a misconfigured deployment fails as a handled error rather
+/// than a panic.
+pub fn resolve_rpc_url(value: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut resolved = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        resolved.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("unterminated `${{` placeholder in '{value}'"))?;
+        let name = &after[..end];
+        let var = std::env::var(name).map_err(|_| {
+            format!("environment variable '{name}' referenced in '{value}' is not set")
+        })?;
+        resolved.push_str(&var);
+        rest = &after[end + 1..];
+    }
+
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Builds a rotating, self-healing HTTP client for a network.
+///
+/// The endpoints form an ordered rotation led by the primary `url`;
+/// [`FailoverClient`] fails over between them with per-endpoint exponential
+/// backoff, jitter, and cooldown. `compute_units_per_second`, when set, caps
+/// the client-wide request rate via a shared token bucket, and `headers` are
+/// attached to every request (e.g. an `Authorization` bearer).
+///
+/// # Arguments
+///
+/// * `urls` - The ordered RPC endpoints; the first is the primary.
+/// * `compute_units_per_second` - An optional per-second request budget.
+/// * `headers` - Custom HTTP headers attached to each request.
+///
+/// # Returns
+///
+/// An `Arc`-wrapped provider, or an error if an endpoint or header is invalid.
+pub fn create_retry_client(
+    urls: &[String],
+    compute_units_per_second: Option<u64>,
+    headers: &[(&str, String)],
+) -> Result<Arc<Provider<FailoverClient>>, Box<dyn std::error::Error>> {
+    if urls.is_empty() {
+        return Err("create_retry_client requires at least one endpoint".into());
+    }
+
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        // Resolve `${VAR}` placeholders from the environment so secrets stay out
+        // of the generated source and surface unset vars as handled errors.
+        let value = resolve_rpc_url(value)?;
+        header_map.insert(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(&value)?,
+        );
+    }
+
+    let client = reqwest::Client::builder()
+        .default_headers(header_map)
+        .build()?;
+
+    let mut endpoints = Vec::with_capacity(urls.len());
+    for url in urls {
+        let url = resolve_rpc_url(url)?;
+        endpoints.push(Endpoint {
+            http: Http::new_with_client(Url::parse(&url)?, client.clone()),
+            backoff: Mutex::new(Backoff::default()),
+        });
+    }
+
+    let rate_limiter = compute_units_per_second
+        .filter(|&cups| cups > 0)
+        .map(|cups| Arc::new(RateLimiter::new(cups as f64)));
+
+    let failover = FailoverClient {
+        endpoints: Arc::new(endpoints),
+        cursor: Arc::new(AtomicUsize::new(0)),
+        rate_limiter,
+    };
+
+    Ok(Arc::new(Provider::new(failover)))
+}
+
+/// Builds a self-healing WebSocket provider for a network.
+///
+/// The transport reconnects transparently when the socket drops, so downstream
+/// `eth_subscribe` streams (`newHeads`/`logs`) survive RPC hiccups without the
+/// caller re-establishing the subscription.
+///
+/// # Arguments
+///
+/// * `url` - The `ws://`/`wss://` endpoint to tail.
+///
+/// # Returns
+///
+/// An `Arc`-wrapped WebSocket provider, or an error if the connection fails.
+///
+/// The socket is established on the caller's runtime so the background
+/// reconnect/pump task `Ws::connect_with_reconnects` spawns stays alive for the
+/// lifetime of the provider. Generated accessors connect lazily on first use
+/// (via a `tokio::sync::OnceCell`) rather than eagerly in a `lazy_static`.
+pub async fn create_ws_client(
+    url: impl AsRef<str>,
+) -> Result<Arc<Provider<Ws>>, Box<dyn std::error::Error>> {
+    // Resolve `${VAR}` placeholders from the environment so a hybrid `ws_url`
+    // secret never lands in the generated source.
+    let url = resolve_rpc_url(url.as_ref())?;
+    let ws = Ws::connect_with_reconnects(&url, WS_MAX_RECONNECTS).await?;
+    let provider = Provider::new(ws).interval(Duration::from_millis(100));
+    Ok(Arc::new(provider))
+}